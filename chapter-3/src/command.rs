@@ -0,0 +1,83 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Parsing of the slash-commands typed on stdin.
+
+use libp2p::Multiaddr;
+
+/// A line typed by the user on stdin, once parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `/dial <multiaddr>`: connect to the given address.
+    Dial(Multiaddr),
+    /// `/subscribe <topic>`: join a topic.
+    Subscribe(String),
+    /// `/unsubscribe <topic>`: leave a topic.
+    Unsubscribe(String),
+    /// `/nick <name>`: change the nickname chat messages are published under.
+    Nick(String),
+    /// A recognized slash-command that was malformed (bad multiaddr, missing argument, ...).
+    /// The problem has already been reported to the user; there's nothing further to do.
+    Noop,
+    /// Anything else: publish the line as-is to the current topic.
+    Publish(String),
+}
+
+/// Parses a line typed on stdin into a `Command`.
+///
+/// Lines starting with `/dial`, `/subscribe`, `/unsubscribe` or `/nick` are turned into the
+/// matching variant, or `Command::Noop` if malformed; anything else (including unrecognized
+/// slash-commands) is treated as a message to publish.
+pub fn parse_line(line: &str) -> Command {
+    let line = line.trim();
+    let mut words = line.splitn(2, char::is_whitespace);
+
+    match (words.next(), words.next()) {
+        (Some("/dial"), Some(addr)) => {
+            match addr.trim().parse() {
+                Ok(addr) => Command::Dial(addr),
+                Err(_) => {
+                    println!("Failed to parse multiaddress: {}", addr);
+                    Command::Noop
+                }
+            }
+        }
+        (Some("/dial"), None) => {
+            println!("Usage: /dial <multiaddr>");
+            Command::Noop
+        }
+        (Some("/subscribe"), Some(topic)) => Command::Subscribe(topic.trim().to_owned()),
+        (Some("/subscribe"), None) => {
+            println!("Usage: /subscribe <topic>");
+            Command::Noop
+        }
+        (Some("/unsubscribe"), Some(topic)) => Command::Unsubscribe(topic.trim().to_owned()),
+        (Some("/unsubscribe"), None) => {
+            println!("Usage: /unsubscribe <topic>");
+            Command::Noop
+        }
+        (Some("/nick"), Some(nick)) => Command::Nick(nick.trim().to_owned()),
+        (Some("/nick"), None) => {
+            println!("Usage: /nick <name>");
+            Command::Noop
+        }
+        _ => Command::Publish(line.to_owned()),
+    }
+}
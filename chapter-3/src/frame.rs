@@ -0,0 +1,53 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Wire framing for chat messages: a nickname plus a body, so that a received message can be
+//! printed as `nick> body` instead of raw, unattributed text.
+
+/// A byte that's vanishingly unlikely to show up in typed chat text, used to separate the nick
+/// from the body. Keeping the format this simple means peers that only ever publish raw text
+/// still interop: their messages just fail to decode and fall back to plain text.
+const SEPARATOR: u8 = 0x01;
+
+/// A parsed chat frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub nick: String,
+    pub body: String,
+}
+
+impl Frame {
+    /// Serializes this frame to the bytes published over floodsub.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = self.nick.clone().into_bytes();
+        bytes.push(SEPARATOR);
+        bytes.extend_from_slice(self.body.as_bytes());
+        bytes
+    }
+
+    /// Parses a frame previously produced by `encode`. Returns `None` if `data` doesn't look
+    /// like a frame, in which case the caller should fall back to treating it as plain text.
+    pub fn decode(data: &[u8]) -> Option<Frame> {
+        let separator = data.iter().position(|&b| b == SEPARATOR)?;
+        let nick = String::from_utf8(data[..separator].to_owned()).ok()?;
+        let body = String::from_utf8(data[separator + 1..].to_owned()).ok()?;
+        Some(Frame { nick, body })
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Loads (or generates once) the keypair a node's `PeerId` is derived from, so that a native
+//! node keeps the same identity across runs instead of becoming a stranger to its peers every
+//! time it restarts.
+
+/// Default location, relative to the current directory, of the persisted keypair.
+const KEYPAIR_PATH: &str = "identity.key";
+
+/// Returns the raw key bytes to derive this node's `PeerId` from.
+///
+/// On native targets this is read from [`KEYPAIR_PATH`], generating and writing a new one on
+/// first run so the same `PeerId` is reused across launches. Under emscripten there's no
+/// filesystem to persist to, so a fresh random key is generated every time.
+#[cfg(not(target_os = "emscripten"))]
+pub fn load_or_generate() -> Vec<u8> {
+    use std::fs;
+    use std::io::{Read, Write};
+
+    if let Ok(mut file) = fs::File::open(KEYPAIR_PATH) {
+        let mut key = Vec::new();
+        file.read_to_end(&mut key).expect("failed to read identity.key");
+        if !key.is_empty() {
+            return key;
+        }
+    }
+
+    let key = generate();
+    fs::File::create(KEYPAIR_PATH)
+        .and_then(|mut file| file.write_all(&key))
+        .expect("failed to write identity.key");
+    key
+}
+
+/// The browser has no filesystem to persist an identity to, so a fresh key is generated every
+/// run.
+#[cfg(target_os = "emscripten")]
+pub fn load_or_generate() -> Vec<u8> {
+    generate()
+}
+
+fn generate() -> Vec<u8> {
+    (0..2048).map(|_| rand::random::<u8>()).collect()
+}
@@ -41,7 +41,8 @@
 //! The browser doesn't support dialing to a TCP port. The only protocol that is allowed is
 //! websockets. Good news, however! The `build_transport()` method in the `platform` module
 //! automatically builds a transport that supports websockets. To use them, instead of dialing
-//! `/ip4/1.2.3.4/tcp/1000`, you can dial `/ip4/1.2.3.4/tcp/1000/ws`.
+//! `/ip4/1.2.3.4/tcp/1000`, you can dial `/ip4/1.2.3.4/tcp/1000/ws`. A TLS-terminated relay can
+//! be dialed the same way using `/wss` instead of `/ws`, e.g. `/dns4/example.com/tcp/443/wss`.
 //!
 //! Additionally, please note that the browser doesn't support listening on any connection (even
 //! websockets). Calling `listen_on` will trigger an error at runtime. You can use
@@ -52,6 +53,17 @@
 extern crate futures;
 extern crate tokio_io;
 
+#[cfg(not(target_os = "emscripten"))]
+extern crate tokio_core;
+#[cfg(not(target_os = "emscripten"))]
+extern crate tokio_stdin_stdout;
+#[cfg(not(target_os = "emscripten"))]
+extern crate tokio_rustls;
+#[cfg(not(target_os = "emscripten"))]
+extern crate webpki;
+#[cfg(not(target_os = "emscripten"))]
+extern crate webpki_roots;
+
 #[cfg(target_os = "emscripten")]
 #[macro_use]
 extern crate stdweb;
@@ -61,13 +73,31 @@ extern crate libp2p;
 extern crate rand;
 //extern crate tokio_stdin;
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use futures::{Future, Stream};
 
 use libp2p::{Multiaddr, PeerId};
 use libp2p::core::Transport;
-use libp2p::floodsub::{FloodSubUpgrade, FloodSubController, TopicBuilder};
-
+use libp2p::floodsub::{FloodSubUpgrade, FloodSubController};
+use libp2p::secio::{SecioConfig, SecioKeyPair};
+use libp2p::mplex;
+
+use command::Command;
+use frame::Frame;
+use topics::Topics;
+
+mod command;
+mod frame;
+mod identity;
+mod nickname;
 mod platform;
+mod topics;
+
+/// The topic joined at startup, and the one `/unsubscribe` falls back to if the user leaves
+/// whatever topic they were currently publishing to.
+const DEFAULT_TOPIC: &str = "workshop-chapter2-topic";
 
 fn main() {
     // The `PlatformSpecific` object allows you to handle the transport and stdin in a
@@ -78,14 +108,23 @@ fn main() {
     // earlier chapters).
     let transport = platform.build_transport();
 
+    // Deriving the `PeerId` from a persisted keypair (native) instead of a fresh random one
+    // means a returning node keeps the same identity across runs.
     let (floodsub_upgrade, floodsub_rx) = {
-        let key = (0..2048).map(|_| rand::random::<u8>()).collect::<Vec<_>>();
+        let key = identity::load_or_generate();
         FloodSubUpgrade::new(PeerId::from_public_key(&key))
     };
-    let upgraded_transport = transport
-        .with_upgrade(floodsub_upgrade.clone());
 
-    let upgr_trans_with_muxing = upgraded_transport.with_dummy_muxing();
+    // Authenticate and encrypt every connection with secio, then open an mplex multiplexer on
+    // top so that floodsub can use several logical substreams over a single connection.
+    let secio_key = SecioKeyPair::ed25519_generated()
+        .expect("failed to generate secio keypair");
+
+    let upgr_trans_with_muxing = transport
+        .with_upgrade(SecioConfig::new(secio_key))
+        .map(|out, _| (out.remote_key.into_peer_id(), out.stream))
+        .with_upgrade(mplex::MplexConfig::new())
+        .with_upgrade(floodsub_upgrade.clone());
     let (swarm_controller, swarm_future) = libp2p::swarm(
         upgr_trans_with_muxing.clone(),
         |future, _remote_addr| {
@@ -100,37 +139,93 @@ fn main() {
         // Let's use the swarm to listen, instead of the raw transport.
         let actual_multiaddr = swarm_controller.listen_on(listen_multiaddr).expect("failed to listen");
         println!("Now listening on {}", actual_multiaddr);
-    }
 
-    let topic = TopicBuilder::new("workshop-chapter2-topic")
-        .build();
+        // If a TLS identity is configured, also listen on /wss so TLS-capable peers can connect
+        // without a separate TLS-terminating relay in front of us.
+        if platform.has_tls_identity() {
+            let listen_wss_multiaddr: Multiaddr = "/ip4/0.0.0.0/tcp/4243/wss"
+                .parse()
+                .expect("failed to parse multiaddress");
+
+            let actual_wss_multiaddr = swarm_controller.listen_on(listen_wss_multiaddr)
+                .expect("failed to listen");
+            println!("Now listening on {}", actual_wss_multiaddr);
+        }
+    }
 
     let floodsub_controller = FloodSubController::new(&floodsub_upgrade);
 
-    floodsub_controller.subscribe(&topic);
+    // `topics` tracks every topic we've joined so far, so that incoming messages and the
+    // `/subscribe` and `/unsubscribe` commands can refer to topics by name instead of by their
+    // raw hash. `current_topic` is where a plain (non-command) line gets published; `/unsubscribe`
+    // falls back to `default_topic` if it turns out to be the one being left.
+    let topics = Rc::new(RefCell::new(Topics::default()));
+    let default_topic = topics.borrow_mut().join(&floodsub_controller, DEFAULT_TOPIC.to_owned());
+    let current_topic = Rc::new(RefCell::new(default_topic.clone()));
 
+    let topics_for_rx = topics.clone();
     let floodsub_rx = floodsub_rx
-        .for_each(|msg| {
-            if let Ok(msg) = String::from_utf8(msg.data) {
-                println!("> {}", msg);
-            } else {
-                println!("Received non-utf8 message");
+        .for_each(move |msg| {
+            let labels = topics_for_rx.borrow().labels_for(&msg.topics);
+            let label = labels.first().cloned().unwrap_or("?");
+
+            // Frames produced by a `/nick`-aware peer decode to `nick> body`; anything else
+            // (including raw-text peers) falls back to printing the bytes unattributed.
+            match Frame::decode(&msg.data) {
+                Some(frame) => println!("[{}] {}> {}", label, frame.nick, frame.body),
+                None => match String::from_utf8(msg.data) {
+                    Ok(text) => println!("[{}] {}", label, text),
+                    Err(_) => println!("[{}] Received non-utf8 message", label),
+                },
             }
 
             Ok(())
         });
 
-    let dial_multiaddr: Multiaddr = "/ip4/127.0.0.1/tcp/4242/ws"
-        .parse()
-        .expect("failed to parse multiaddress");
-    swarm_controller.dial(dial_multiaddr, upgr_trans_with_muxing.clone()).expect("Failed to dial");
-
     // This builds a stream of messages coming from stdin.
     let stdin = platform.stdin();
 
-    // Insert your code here!
-    let stdin_future = stdin.for_each(move |msg| {
-        floodsub_controller.publish(&topic, msg.into_bytes());
+    // `nick` is what outgoing messages are framed and published under; it starts out as a
+    // randomly-generated human-friendly name and can be changed at runtime with `/nick`.
+    let nick = Rc::new(RefCell::new(nickname::random_nickname()));
+    println!("Your nickname is {} (change it with /nick <name>)", nick.borrow());
+
+    // Lines typed on stdin are parsed into slash-commands; `/dial` lets a peer connect to a
+    // relay at runtime instead of requiring one to be listening before launch,
+    // `/subscribe`/`/unsubscribe` drive which topics we're a part of, and `/nick` changes the
+    // name chat messages are published under.
+    let stdin_future = stdin.for_each(move |line| {
+        match command::parse_line(&line) {
+            Command::Dial(addr) => {
+                match swarm_controller.dial(addr.clone(), upgr_trans_with_muxing.clone()) {
+                    Ok(()) => println!("Dialing {}", addr),
+                    Err(addr) => println!("Failed to dial {}", addr),
+                }
+            }
+            Command::Subscribe(topic_name) => {
+                let topic = topics.borrow_mut().join(&floodsub_controller, topic_name);
+                *current_topic.borrow_mut() = topic;
+            }
+            Command::Unsubscribe(topic_name) => {
+                let left_hash = topics.borrow_mut().leave(&floodsub_controller, &topic_name);
+                if left_hash.as_ref() == Some(current_topic.borrow().hash()) {
+                    // We just left the topic we were publishing to: fall back to the default
+                    // room instead of silently publishing into a topic nobody (including us)
+                    // is listening on anymore. If the topic left is the default one itself,
+                    // publishing stays on it even though we no longer receive from it.
+                    *current_topic.borrow_mut() = default_topic.clone();
+                    println!("Left current topic, now publishing to {} again", DEFAULT_TOPIC);
+                }
+            }
+            Command::Nick(new_nick) => {
+                *nick.borrow_mut() = new_nick;
+            }
+            Command::Noop => {}
+            Command::Publish(body) => {
+                let frame = Frame { nick: nick.borrow().clone(), body };
+                floodsub_controller.publish(&*current_topic.borrow(), frame.encode());
+            }
+        }
 
         Ok(())
     });
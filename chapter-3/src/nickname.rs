@@ -0,0 +1,37 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Generates a random human-friendly nickname at startup, so a node has something better than
+//! its peer ID to introduce itself with before the user picks one via `/nick`.
+
+const ADJECTIVES: &[&str] = &[
+    "swift", "quiet", "brave", "lucky", "curious", "gentle", "bold", "clever",
+];
+
+const ANIMALS: &[&str] = &[
+    "otter", "falcon", "panda", "lynx", "sparrow", "badger", "heron", "wolf",
+];
+
+/// Picks a random `adjective-animal` nickname, e.g. `swift-otter`.
+pub fn random_nickname() -> String {
+    let adjective = ADJECTIVES[rand::random::<usize>() % ADJECTIVES.len()];
+    let animal = ANIMALS[rand::random::<usize>() % ANIMALS.len()];
+    format!("{}-{}", adjective, animal)
+}
@@ -0,0 +1,65 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Emscripten (in-browser) implementation of `PlatformSpecific`.
+
+use futures::{Future, Stream};
+use libp2p::websocket::BrowserWsConfig;
+use std::io;
+use stdweb::web;
+
+/// Runs an events loop and reads stdin (via a `<textarea>`) inside of a browser.
+pub struct PlatformSpecific;
+
+impl Default for PlatformSpecific {
+    fn default() -> PlatformSpecific {
+        PlatformSpecific
+    }
+}
+
+impl PlatformSpecific {
+    /// Builds the transport used to dial connections.
+    ///
+    /// The browser can only dial websockets, so this is the only transport available here.
+    /// Dialing a `/wss` multiaddress works out of the box: the browser's own `WebSocket`
+    /// implementation negotiates TLS transparently for `wss://` URLs, no extra layering needed.
+    pub fn build_transport(&self) -> BrowserWsConfig {
+        BrowserWsConfig::new()
+    }
+
+    /// The browser can't listen on anything, so there's never a server identity to listen
+    /// `/wss` with.
+    pub fn has_tls_identity(&self) -> bool {
+        false
+    }
+
+    /// Returns a stream that produces one item per line typed in the page's input box.
+    pub fn stdin(&self) -> impl Stream<Item = String, Error = io::Error> {
+        web::stdin_stream()
+    }
+
+    /// Runs the given future on the browser's events loop.
+    pub fn run<F>(&self, future: F)
+    where
+        F: Future<Item = (), Error = io::Error> + 'static,
+    {
+        stdweb::spawn(future.map_err(|err| panic!("event loop error: {:?}", err)));
+    }
+}
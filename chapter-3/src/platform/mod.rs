@@ -0,0 +1,37 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Platform-specific glue code.
+//!
+//! This module hides the differences between running natively and running inside of a browser
+//! through emscripten behind a single `PlatformSpecific` type. `main()` is expected to only ever
+//! use the items re-exported here.
+
+#[cfg(not(target_os = "emscripten"))]
+mod native;
+#[cfg(not(target_os = "emscripten"))]
+mod tls;
+#[cfg(not(target_os = "emscripten"))]
+pub use self::native::PlatformSpecific;
+
+#[cfg(target_os = "emscripten")]
+mod emscripten;
+#[cfg(target_os = "emscripten")]
+pub use self::emscripten::PlatformSpecific;
@@ -0,0 +1,121 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Native (non-emscripten) implementation of `PlatformSpecific`.
+
+use futures::{Future, Stream};
+use libp2p::core::transport::OrTransport;
+use libp2p::core::Transport;
+use libp2p::dns::DnsConfig;
+use libp2p::tcp::TcpConfig;
+use libp2p::websocket::WsConfig;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::Path;
+use tokio_core::reactor::Core;
+use tokio_rustls::rustls;
+
+use super::tls::TlsConfig;
+
+const TLS_CERT_PATH: &str = "tls_cert.pem";
+const TLS_KEY_PATH: &str = "tls_key.pem";
+
+/// Runs an events loop and reads stdin on a native target.
+pub struct PlatformSpecific {
+    core: RefCell<Core>,
+}
+
+impl Default for PlatformSpecific {
+    fn default() -> PlatformSpecific {
+        PlatformSpecific {
+            core: RefCell::new(Core::new().expect("failed to create event loop")),
+        }
+    }
+}
+
+impl PlatformSpecific {
+    /// Builds the transport used to dial and listen for connections.
+    ///
+    /// Wraps a DNS-resolving TCP transport in a websocket layer, so that both literal
+    /// `/ip4/.../tcp/.../ws` and name-based `/dns4/.../tcp/.../ws` multiaddresses can be dialed,
+    /// and in a TLS layer on top of that so the `/wss` equivalents negotiate TLS before the
+    /// websocket handshake.
+    pub fn build_transport(
+        &self,
+    ) -> OrTransport<WsConfig<DnsConfig<TcpConfig>>, WsConfig<TlsConfig<DnsConfig<TcpConfig>>>> {
+        let tcp = DnsConfig::new(TcpConfig::new(self.core.borrow().handle()));
+        let ws = WsConfig::new(tcp.clone());
+        let wss = WsConfig::new(self.build_tls(tcp));
+        ws.or_transport(wss)
+    }
+
+    /// Builds the TLS layer used under `/wss`. If a `tls_cert.pem`/`tls_key.pem` pair is found
+    /// in the current directory, listening on `/wss` is enabled with that identity;
+    /// otherwise the returned config can still be used to dial.
+    fn build_tls(&self, tcp: DnsConfig<TcpConfig>) -> TlsConfig<DnsConfig<TcpConfig>> {
+        let tls = TlsConfig::new(tcp);
+
+        if !self.has_tls_identity() {
+            return tls;
+        }
+
+        let cert_chain = load_cert_chain(Path::new(TLS_CERT_PATH)).expect("failed to read tls_cert.pem");
+        let private_key = load_private_key(Path::new(TLS_KEY_PATH)).expect("failed to read tls_key.pem");
+        tls.with_server_identity(cert_chain, private_key)
+            .expect("failed to apply TLS identity")
+    }
+
+    /// Whether a `tls_cert.pem`/`tls_key.pem` pair is present in the current directory,
+    /// i.e. whether `main()` can listen on a `/wss` multiaddress in addition to `/ws`.
+    pub fn has_tls_identity(&self) -> bool {
+        Path::new(TLS_CERT_PATH).exists() && Path::new(TLS_KEY_PATH).exists()
+    }
+
+    /// Returns a stream that produces one item per line typed on stdin.
+    pub fn stdin(&self) -> impl Stream<Item = String, Error = io::Error> {
+        tokio_stdin_stdout::spawn_stdin_stream_unbounded()
+            .map(|buf| String::from_utf8_lossy(&buf).into_owned())
+            .map_err(|()| io::Error::new(io::ErrorKind::Other, "failed to read from stdin"))
+    }
+
+    /// Runs the given future to completion on the events loop.
+    pub fn run<F>(&self, future: F)
+    where
+        F: Future<Item = (), Error = io::Error>,
+    {
+        self.core.borrow_mut().run(future).expect("event loop error");
+    }
+}
+
+/// Reads a PEM-encoded certificate chain from disk.
+fn load_cert_chain(path: &Path) -> io::Result<Vec<rustls::Certificate>> {
+    let mut reader = io::BufReader::new(fs::File::open(path)?);
+    rustls::internal::pemfile::certs(&mut reader)
+        .map_err(|()| io::Error::new(io::ErrorKind::InvalidData, "invalid certificate"))
+}
+
+/// Reads a PEM-encoded PKCS#8 private key from disk.
+fn load_private_key(path: &Path) -> io::Result<rustls::PrivateKey> {
+    let mut reader = io::BufReader::new(fs::File::open(path)?);
+    let mut keys = rustls::internal::pemfile::pkcs8_private_keys(&mut reader)
+        .map_err(|()| io::Error::new(io::ErrorKind::InvalidData, "invalid private key"))?;
+    keys.pop().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no private key found"))
+}
@@ -0,0 +1,131 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! A `Transport` wrapper that terminates TLS (via rustls) on top of an inner stream-based
+//! transport, used to support `/wss` multiaddresses.
+
+use std::io;
+use std::sync::Arc;
+
+use futures::{Future, Stream};
+use libp2p::core::{Multiaddr, Transport};
+use tokio_rustls::{rustls, TlsAcceptor, TlsConnector};
+use webpki_roots;
+
+/// Wraps an inner transport `T` and runs a TLS handshake over every connection it opens or
+/// accepts, before handing the decrypted stream to whatever is layered on top (typically
+/// `WsConfig`).
+#[derive(Clone)]
+pub struct TlsConfig<T> {
+    inner: T,
+    connector: TlsConnector,
+    acceptor: Option<TlsAcceptor>,
+}
+
+impl<T> TlsConfig<T> {
+    /// Builds a client-only `TlsConfig`, trusting the default web PKI root certificates.
+    pub fn new(inner: T) -> TlsConfig<T> {
+        let mut client_config = rustls::ClientConfig::new();
+        client_config.root_store.add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+
+        TlsConfig {
+            inner,
+            connector: TlsConnector::from(Arc::new(client_config)),
+            acceptor: None,
+        }
+    }
+
+    /// Enables listening on `/wss` by additionally accepting TLS connections using the given
+    /// certificate chain and private key.
+    pub fn with_server_identity(
+        mut self,
+        cert_chain: Vec<rustls::Certificate>,
+        private_key: rustls::PrivateKey,
+    ) -> Result<TlsConfig<T>, rustls::TLSError> {
+        let mut server_config = rustls::ServerConfig::new(rustls::NoClientAuth::new());
+        server_config.set_single_cert(cert_chain, private_key)?;
+        self.acceptor = Some(TlsAcceptor::from(Arc::new(server_config)));
+        Ok(self)
+    }
+}
+
+impl<T> Transport for TlsConfig<T>
+where
+    T: Transport,
+    T::Output: io::Read + io::Write,
+{
+    type Output = Box<dyn io::Read + io::Write + Send>;
+    type Listener = Box<dyn Stream<Item = (Self::ListenerUpgrade, Multiaddr), Error = io::Error>>;
+    type ListenerUpgrade = Box<dyn Future<Item = Self::Output, Error = io::Error>>;
+    type Dial = Box<dyn Future<Item = Self::Output, Error = io::Error>>;
+
+    fn listen_on(self, addr: Multiaddr) -> Result<(Self::Listener, Multiaddr), (Self, Multiaddr)> {
+        let acceptor = match self.acceptor.clone() {
+            Some(acceptor) => acceptor,
+            None => return Err((self, addr)),
+        };
+
+        let (inner_listener, listen_addr) = match self.inner.clone().listen_on(addr) {
+            Ok(ok) => ok,
+            Err((_, addr)) => return Err((self, addr)),
+        };
+
+        let listener = inner_listener.map(move |(upgrade, remote_addr)| {
+            let acceptor = acceptor.clone();
+            let upgrade: Self::ListenerUpgrade = Box::new(
+                upgrade.and_then(move |stream| acceptor.accept(stream))
+                    .map(|stream| Box::new(stream) as Self::Output),
+            );
+            (upgrade, remote_addr)
+        });
+
+        Ok((Box::new(listener), listen_addr))
+    }
+
+    fn dial(self, addr: Multiaddr) -> Result<Self::Dial, (Self, Multiaddr)> {
+        let connector = self.connector.clone();
+        let domain = match dns_name_from_multiaddr(&addr) {
+            Some(domain) => domain,
+            None => return Err((self, addr)),
+        };
+
+        let dial = match self.inner.clone().dial(addr) {
+            Ok(dial) => dial,
+            Err((_, addr)) => return Err((self, addr)),
+        };
+
+        let dial = dial
+            .and_then(move |stream| connector.connect(domain.as_ref(), stream))
+            .map(|stream| Box::new(stream) as Self::Output);
+
+        Ok(Box::new(dial))
+    }
+}
+
+/// Pulls the `/dns4/<name>` or `/dns6/<name>` component out of a multiaddress, since that's what
+/// rustls needs for SNI and certificate validation.
+fn dns_name_from_multiaddr(addr: &Multiaddr) -> Option<webpki::DNSName> {
+    addr.iter().find_map(|component| match component {
+        ::libp2p::multiaddr::Protocol::Dns4(name) | ::libp2p::multiaddr::Protocol::Dns6(name) => {
+            webpki::DNSNameRef::try_from_ascii_str(&name).ok().map(|n| n.to_owned())
+        }
+        _ => None,
+    })
+}
@@ -0,0 +1,67 @@
+// Copyright 2018 Pierre Krieger
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the "Software"),
+// to deal in the Software without restriction, including without limitation
+// the rights to use, copy, modify, merge, publish, distribute, sublicense,
+// and/or sell copies of the Software, and to permit persons to whom the
+// Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Keeps track of the topics we're currently subscribed to, so that incoming messages can be
+//! tagged with a human-readable label instead of their raw topic hash.
+
+use std::collections::HashMap;
+
+use libp2p::floodsub::{FloodSubController, Topic, TopicBuilder, TopicHash};
+
+/// A registry of the topics subscribed to, keyed by topic hash so that incoming messages (which
+/// only carry hashes) can be matched back to the name the user joined with.
+#[derive(Default)]
+pub struct Topics {
+    subscribed: HashMap<TopicHash, String>,
+}
+
+impl Topics {
+    /// Subscribes to a topic by name, returning the `Topic` to publish on.
+    ///
+    /// Joining a topic that's already subscribed to is a no-op beyond rebuilding the `Topic`
+    /// value, which is cheap.
+    pub fn join(&mut self, controller: &FloodSubController, name: String) -> Topic {
+        let topic = TopicBuilder::new(name.clone()).build();
+        controller.subscribe(&topic);
+        self.subscribed.insert(topic.hash().clone(), name);
+        topic
+    }
+
+    /// Unsubscribes from a topic previously joined by name, returning its hash if it was indeed
+    /// subscribed. Callers publishing to the current topic by hash (e.g. `main`'s
+    /// `current_topic`) should compare against this to notice when the topic they were about to
+    /// leave was the one they were publishing to.
+    pub fn leave(&mut self, controller: &FloodSubController, name: &str) -> Option<TopicHash> {
+        let topic = TopicBuilder::new(name).build();
+        if self.subscribed.remove(topic.hash()).is_some() {
+            controller.unsubscribe(&topic);
+            Some(topic.hash().clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the display labels of the joined topics a message was published on, in order.
+    pub fn labels_for(&self, hashes: &[TopicHash]) -> Vec<&str> {
+        hashes.iter()
+            .filter_map(|hash| self.subscribed.get(hash).map(String::as_str))
+            .collect()
+    }
+}